@@ -70,6 +70,12 @@ pub fn c_var_enum(tokens: TokenStream) -> TokenStream {
     let (idents, names): (Vec<_>, Vec<String>) = idents.into_iter().unzip();
     let count = idents.len();
 
+    let primary_idents: Vec<_> = variants.iter().map(|variant| variant.ident.clone()).collect();
+    let primary_names: Vec<_> = primary_idents
+        .iter()
+        .map(|ident| ident.to_string().to_case(convert_case::Case::Snake))
+        .collect();
+
     quote! {
         impl cvars::Value for #ident {
             fn parse(s: &str) -> Result<Self, cvars::Error> {
@@ -101,6 +107,14 @@ pub fn c_var_enum(tokens: TokenStream) -> TokenStream {
 
                 Ok(values)
             }
+
+            fn to_config_string(&self) -> String {
+                match self {
+                    #(
+                        #ident::#primary_idents => #primary_names.to_string(),
+                    )*
+                }
+            }
         }
     }
     .into()
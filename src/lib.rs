@@ -1,11 +1,20 @@
 pub use proc::CVarEnum;
 
+mod parse;
+mod registry;
+
+pub use parse::{parse_line, ParseError, Span, Statement};
+pub use registry::{Completion, CVarRegistry, ExecError, RegisteredCVar};
+
 use std::{
     num::{
         IntErrorKind, NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize,
         NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, ParseIntError,
     },
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
 };
 
 // Valid types
@@ -36,20 +45,191 @@ use std::{
 // map <name:string> [gamemode:string]
 // Changes the map to map with matching name with optional gamemode specified.
 
+type Constraint<T> = Box<dyn Fn(&T) -> Result<(), Error> + Send + Sync>;
+type OnChangeCallback<T> = Box<dyn Fn(&T) + Send + Sync>;
+
 #[derive(Clone)]
 pub struct CVar<T: Value>(Arc<InnerCVar<T>>);
 
-impl<T: Value> CVar<T> {}
+impl<T: Value> CVar<T> {
+    pub fn name(&self) -> &'static str {
+        self.0.name
+    }
+
+    pub fn description(&self) -> &'static str {
+        self.0.description
+    }
+
+    /// Whether [`CVarRegistry::write_config`] should persist this variable.
+    /// Defaults to `true`.
+    pub fn is_persistent(&self) -> bool {
+        self.0.persistent.load(Ordering::Relaxed)
+    }
+
+    pub fn set_persistent(&self, persistent: bool) {
+        self.0.persistent.store(persistent, Ordering::Relaxed);
+    }
+}
+
+impl<T: Value + Clone> CVar<T> {
+    pub fn new(name: &'static str, description: &'static str, default: T) -> Self {
+        Self::with_constraint(name, description, default, None)
+    }
+
+    fn with_constraint(
+        name: &'static str,
+        description: &'static str,
+        default: T,
+        constraint: Option<Constraint<T>>,
+    ) -> Self {
+        Self(Arc::new(InnerCVar {
+            name,
+            description,
+            value: RwLock::new(default),
+            constraint,
+            on_change: RwLock::new(Vec::new()),
+            persistent: AtomicBool::new(true),
+        }))
+    }
+
+    pub fn get(&self) -> T {
+        self.0.value.read().unwrap().clone()
+    }
+
+    /// Registers a callback to run every time this variable's value
+    /// changes, whether set directly or through `set_from_str`.
+    ///
+    /// Callbacks run after the write lock on the value is released, so they
+    /// may read this `CVar` (e.g. `get`) without deadlocking. The callback
+    /// list itself stays read-locked while they run, so calling `on_change`
+    /// or `set` on this `CVar` from inside a callback is not safe.
+    pub fn on_change<F>(&self, callback: F)
+    where
+        F: Fn(&T) + Send + Sync + 'static,
+    {
+        self.0.on_change.write().unwrap().push(Box::new(callback));
+    }
+
+    pub fn set(&self, value: T) -> Result<(), Error> {
+        self.check_constraint(&value)?;
+
+        {
+            *self.0.value.write().unwrap() = value.clone();
+        }
+
+        for callback in self.0.on_change.read().unwrap().iter() {
+            callback(&value);
+        }
+
+        Ok(())
+    }
+
+    pub fn set_from_str(&self, s: &str) -> Result<(), Error> {
+        let value = T::parse(s)?;
+
+        self.set(value)
+    }
+
+    /// Like `Value::validate`, but drops any candidate that would fail this
+    /// variable's constraint (e.g. an out-of-range number), so completion
+    /// never offers an invalid value.
+    pub fn validate(&self, s: &str) -> Result<Vec<String>, Error> {
+        let candidates = T::validate(s)?;
+
+        Ok(candidates
+            .into_iter()
+            .filter(|candidate| {
+                T::parse(candidate)
+                    .map(|value| self.check_constraint(&value).is_ok())
+                    .unwrap_or(false)
+            })
+            .collect())
+    }
+
+    fn check_constraint(&self, value: &T) -> Result<(), Error> {
+        match &self.0.constraint {
+            Some(constraint) => constraint(value),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T> CVar<T>
+where
+    T: Value + Clone + PartialOrd + ToString + Send + Sync + 'static,
+{
+    /// Constrains this variable's value to `range`, rejecting (and refusing
+    /// to complete) anything outside it.
+    pub fn new_ranged(
+        name: &'static str,
+        description: &'static str,
+        default: T,
+        range: std::ops::RangeInclusive<T>,
+    ) -> Self {
+        let (min, max) = range.into_inner();
+
+        let constraint: Constraint<T> = Box::new(move |value: &T| {
+            if *value < min {
+                Err(Error::too_small(
+                    &value.to_string(),
+                    &min.to_string(),
+                    &max.to_string(),
+                ))
+            } else if *value > max {
+                Err(Error::too_large(
+                    &value.to_string(),
+                    &min.to_string(),
+                    &max.to_string(),
+                ))
+            } else {
+                Ok(())
+            }
+        });
+
+        Self::with_constraint(name, description, default, Some(constraint))
+    }
+}
+
+impl CVar<String> {
+    /// Constrains this variable's length (in bytes) to `length`.
+    pub fn new_length_bounded(
+        name: &'static str,
+        description: &'static str,
+        default: String,
+        length: std::ops::RangeInclusive<usize>,
+    ) -> Self {
+        let (min, max) = length.into_inner();
+
+        let constraint: Constraint<String> = Box::new(move |value: &String| {
+            if value.len() < min {
+                Err(Error::too_small(value, &min.to_string(), &max.to_string()))
+            } else if value.len() > max {
+                Err(Error::too_large(value, &min.to_string(), &max.to_string()))
+            } else {
+                Ok(())
+            }
+        });
+
+        Self::with_constraint(name, description, default, Some(constraint))
+    }
+}
 
 pub struct InnerCVar<T: Value> {
     name: &'static str,
     description: &'static str,
     value: RwLock<T>,
+    constraint: Option<Constraint<T>>,
+    on_change: RwLock<Vec<OnChangeCallback<T>>>,
+    persistent: AtomicBool,
 }
 
 pub trait Value: Sized {
     fn parse(s: &str) -> Result<Self, Error>;
     fn validate(s: &str) -> Result<Vec<String>, Error>;
+
+    /// Renders this value exactly as `parse` expects it back, so it can be
+    /// written to a config file and `exec`'d again.
+    fn to_config_string(&self) -> String;
 }
 
 #[derive(Debug, Clone)]
@@ -68,6 +248,9 @@ pub enum Error {
         min: String,
         max: String,
     },
+    UnknownCVar {
+        name: String,
+    },
 }
 
 impl Error {
@@ -96,6 +279,13 @@ impl Error {
         }
     }
 
+    #[inline]
+    pub fn unknown_cvar(name: &str) -> Self {
+        Self::UnknownCVar {
+            name: name.to_string(),
+        }
+    }
+
     pub fn from_parse_int_error(e: ParseIntError, value: &str, min: &str, max: &str) -> Self {
         match e.kind() {
             IntErrorKind::Empty => Self::EmptyValue,
@@ -108,6 +298,24 @@ impl Error {
     }
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidValue { value } => write!(f, "invalid value: \"{value}\""),
+            Self::EmptyValue => write!(f, "value must not be empty"),
+            Self::TooBig { value, min, max } => {
+                write!(f, "\"{value}\" is too big (expected {min}..={max})")
+            }
+            Self::TooSmall { value, min, max } => {
+                write!(f, "\"{value}\" is too small (expected {min}..={max})")
+            }
+            Self::UnknownCVar { name } => write!(f, "unknown cvar: \"{name}\""),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 // impl From<ParseFloatError> for Error {
 //     fn from(_value: ParseFloatError) -> Self {
 //         Self::InvalidValue
@@ -140,6 +348,10 @@ impl Value for bool {
 
         Ok(values)
     }
+
+    fn to_config_string(&self) -> String {
+        self.to_string()
+    }
 }
 
 impl Value for String {
@@ -158,6 +370,13 @@ impl Value for String {
             Err(Error::invalid_value(s))
         }
     }
+
+    /// Inner `"` and line breaks are written unescaped, so a value holding
+    /// them generally does not read back through `CVarRegistry::exec`,
+    /// which reports that line as an error.
+    fn to_config_string(&self) -> String {
+        format!("\"{}\"", self)
+    }
 }
 
 macro_rules! impl_value_int {
@@ -175,6 +394,10 @@ macro_rules! impl_value_int {
 
                     Ok(vec![])
                 }
+
+                fn to_config_string(&self) -> String {
+                    self.to_string()
+                }
             }
         )+
     };
@@ -195,6 +418,10 @@ macro_rules! impl_value_float {
 
                     Ok(vec![])
                 }
+
+                fn to_config_string(&self) -> String {
+                    self.to_string()
+                }
             }
         )+
     };
@@ -0,0 +1,160 @@
+//! Splits a raw console line into statements of whitespace-delimited tokens.
+//!
+//! Grammar (see the comment block in `lib.rs`):
+//! `[<namespace>.]<name> <value>`, statements separated by `;`, `//` runs to
+//! end of line as a comment, and `"..."` tokens may contain whitespace.
+
+/// A byte range into the input a [`Statement`] or [`ParseError`] was derived from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// One `[<namespace>.]<name> <value>...` command parsed out of a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statement {
+    pub name: String,
+    pub name_span: Span,
+    pub args: Vec<String>,
+    pub arg_spans: Vec<Span>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub span: Span,
+}
+
+/// Tokenizes a console line into statements.
+///
+/// Leading whitespace is skipped, `//` runs to the end of the line as a
+/// comment, and `;` separates statements (empty statements between
+/// consecutive `;` are ignored). Tokens are whitespace-delimited, except a
+/// token starting with `"` consumes everything up to the matching closing
+/// `"` (honoring `\"` and `\\` escapes) so it can contain whitespace; the
+/// surrounding quotes are kept, since `String::parse` expects them.
+pub fn parse_line(input: &str) -> Result<Vec<Statement>, ParseError> {
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut statements = Vec::new();
+    let mut i = 0;
+
+    while i < len {
+        i = skip_whitespace_and_comments(input, i);
+
+        if i >= len {
+            break;
+        }
+
+        if bytes[i] == b';' {
+            i += 1;
+            continue;
+        }
+
+        let mut tokens = Vec::new();
+        let mut spans = Vec::new();
+
+        loop {
+            i = skip_whitespace_and_comments(input, i);
+
+            if i >= len || bytes[i] == b';' {
+                break;
+            }
+
+            let (token, span, next) = read_token(input, i)?;
+            tokens.push(token);
+            spans.push(span);
+            i = next;
+        }
+
+        if tokens.is_empty() {
+            continue;
+        }
+
+        let name = tokens.remove(0);
+        let name_span = spans.remove(0);
+
+        statements.push(Statement {
+            name,
+            name_span,
+            args: tokens,
+            arg_spans: spans,
+        });
+    }
+
+    Ok(statements)
+}
+
+/// Advances past whitespace and `//` comments, stopping at the next token,
+/// `;`, or the end of input.
+fn skip_whitespace_and_comments(input: &str, mut i: usize) -> usize {
+    let len = input.len();
+
+    loop {
+        while let Some(c) = input[i..].chars().next() {
+            if !c.is_whitespace() {
+                break;
+            }
+
+            i += c.len_utf8();
+        }
+
+        if input[i..].starts_with("//") {
+            while i < len && input.as_bytes()[i] != b'\n' {
+                i += 1;
+            }
+            continue;
+        }
+
+        break;
+    }
+
+    i
+}
+
+/// Reads a single token starting at `i`, returning the token text, its span,
+/// and the index just past it.
+fn read_token(input: &str, i: usize) -> Result<(String, Span, usize), ParseError> {
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let start = i;
+
+    if bytes[i] == b'"' {
+        let mut j = i + 1;
+
+        loop {
+            if j >= len {
+                return Err(ParseError {
+                    message: "unterminated quoted token".to_string(),
+                    span: Span { start, end: len },
+                });
+            }
+
+            match bytes[j] {
+                b'\\' if j + 1 < len && matches!(bytes[j + 1], b'"' | b'\\') => {
+                    j += 2;
+                }
+                b'"' => {
+                    j += 1;
+                    break;
+                }
+                _ => j += 1,
+            }
+        }
+
+        return Ok((input[start..j].to_string(), Span { start, end: j }, j));
+    }
+
+    let mut j = i;
+
+    while let Some(c) = input[j..].chars().next() {
+        if c.is_whitespace() || c == ';' || c == '"' {
+            break;
+        }
+
+        j += c.len_utf8();
+    }
+
+    Ok((input[start..j].to_string(), Span { start, end: j }, j))
+}
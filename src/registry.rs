@@ -0,0 +1,226 @@
+//! A type-erased collection of [`CVar`]s, addressable by their `name` string.
+
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+};
+
+use crate::{parse_line, CVar, Error, Span, Value};
+
+/// Object-safe view of a `CVar<T>` for some `T`, so a [`CVarRegistry`] can
+/// hold variables of different types behind one map.
+pub trait RegisteredCVar: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn set_from_str(&self, s: &str) -> Result<(), Error>;
+    fn get_as_str(&self) -> String;
+    fn validate(&self, s: &str) -> Result<Vec<String>, Error>;
+    fn is_persistent(&self) -> bool;
+    fn to_config_string(&self) -> String;
+}
+
+impl<T> RegisteredCVar for CVar<T>
+where
+    T: Value + Clone + Send + Sync + 'static,
+{
+    fn name(&self) -> &'static str {
+        CVar::name(self)
+    }
+
+    fn description(&self) -> &'static str {
+        CVar::description(self)
+    }
+
+    fn set_from_str(&self, s: &str) -> Result<(), Error> {
+        CVar::set_from_str(self, s)
+    }
+
+    fn get_as_str(&self) -> String {
+        Value::to_config_string(&self.get())
+    }
+
+    fn validate(&self, s: &str) -> Result<Vec<String>, Error> {
+        CVar::validate(self, s)
+    }
+
+    fn is_persistent(&self) -> bool {
+        CVar::is_persistent(self)
+    }
+
+    fn to_config_string(&self) -> String {
+        Value::to_config_string(&self.get())
+    }
+}
+
+/// Looks up and dispatches to registered [`CVar`]s by name, so a console
+/// line produced by [`crate::parse_line`] can be routed to the right
+/// variable.
+#[derive(Default)]
+pub struct CVarRegistry {
+    cvars: HashMap<String, Box<dyn RegisteredCVar>>,
+}
+
+impl CVarRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<T>(&mut self, cvar: CVar<T>)
+    where
+        T: Value + Clone + Send + Sync + 'static,
+    {
+        self.cvars.insert(cvar.name().to_string(), Box::new(cvar));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn RegisteredCVar> {
+        self.cvars.get(name).map(|cvar| cvar.as_ref())
+    }
+
+    pub fn set(&self, name: &str, value: &str) -> Result<(), Error> {
+        self.get(name)
+            .ok_or_else(|| Error::unknown_cvar(name))?
+            .set_from_str(value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn RegisteredCVar> {
+        self.cvars.values().map(|cvar| cvar.as_ref())
+    }
+
+    /// Completes a partially typed console line.
+    ///
+    /// Before the first space, candidates are registered names starting
+    /// with `partial` (matched as a whole, so `r.` completes every `r.*`
+    /// variable). Once a name is followed by a space, completion delegates
+    /// to that variable's `validate` for the value portion.
+    pub fn complete(&self, partial: &str) -> Vec<Completion> {
+        match partial.find(' ') {
+            None => self
+                .cvars
+                .keys()
+                .filter(|name| name.starts_with(partial))
+                .map(|name| Completion {
+                    text: name.clone(),
+                    span: Span {
+                        start: 0,
+                        end: partial.len(),
+                    },
+                })
+                .collect(),
+            Some(space) => {
+                let name = &partial[..space];
+                let value_start = space + 1;
+                let value_partial = &partial[value_start..];
+
+                self.get(name)
+                    .and_then(|cvar| cvar.validate(value_partial).ok())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|text| Completion {
+                        text,
+                        span: Span {
+                            start: value_start,
+                            end: partial.len(),
+                        },
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Writes one `name value` line per persistent registered variable, with
+    /// values rendered via `Value::to_config_string` so `exec` can read them
+    /// back.
+    pub fn write_config<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut names: Vec<_> = self.cvars.keys().collect();
+        names.sort();
+
+        for name in names {
+            let cvar = &self.cvars[name];
+
+            if !cvar.is_persistent() {
+                continue;
+            }
+
+            writeln!(writer, "{} {}", cvar.name(), cvar.to_config_string())?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads `name value` lines (as written by `write_config`) and applies
+    /// each to the matching registered variable, tokenizing every line with
+    /// [`crate::parse_line`]. A bad line does not abort the rest of the
+    /// file; every failure is collected with its 1-based line number.
+    pub fn exec<R: BufRead>(&self, reader: R) -> Result<(), Vec<ExecError>> {
+        let mut errors = Vec::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line_number = index + 1;
+
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    errors.push(ExecError {
+                        line: line_number,
+                        message: e.to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            let statements = match parse_line(&line) {
+                Ok(statements) => statements,
+                Err(e) => {
+                    errors.push(ExecError {
+                        line: line_number,
+                        message: e.message,
+                    });
+                    continue;
+                }
+            };
+
+            for statement in statements {
+                if statement.args.len() != 1 {
+                    errors.push(ExecError {
+                        line: line_number,
+                        message: format!(
+                            "expected exactly one value for \"{}\", got {}",
+                            statement.name,
+                            statement.args.len()
+                        ),
+                    });
+                    continue;
+                }
+
+                if let Err(e) = self.set(&statement.name, &statement.args[0]) {
+                    errors.push(ExecError {
+                        line: line_number,
+                        message: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// A single failure from [`CVarRegistry::exec`], tied to the 1-based line
+/// that caused it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// A completion candidate for a console line: the replacement text, and the
+/// byte span of `partial` it replaces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    pub text: String,
+    pub span: Span,
+}
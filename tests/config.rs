@@ -0,0 +1,100 @@
+use cvars::{CVar, CVarRegistry};
+
+#[test]
+fn write_config_round_trips_through_exec() {
+    let mut registry = CVarRegistry::new();
+    registry.register(CVar::new("r.full_bright", "Disables all lighting.", false));
+    registry.register(CVar::new_ranged(
+        "net.port",
+        "Listen port.",
+        1024u32,
+        512..=2048,
+    ));
+    registry.register(CVar::new(
+        "player.name",
+        "Display name.",
+        "anon".to_string(),
+    ));
+
+    registry.set("r.full_bright", "true").unwrap();
+    registry.set("net.port", "1500").unwrap();
+    registry.set("player.name", "\"someone\"").unwrap();
+
+    let mut config = Vec::new();
+    registry.write_config(&mut config).unwrap();
+    let config = String::from_utf8(config).unwrap();
+
+    assert!(config.contains("net.port 1500\n"));
+    assert!(config.contains("player.name \"someone\"\n"));
+    assert!(config.contains("r.full_bright true\n"));
+
+    let mut replayed = CVarRegistry::new();
+    replayed.register(CVar::new("r.full_bright", "Disables all lighting.", false));
+    replayed.register(CVar::new_ranged(
+        "net.port",
+        "Listen port.",
+        1024u32,
+        512..=2048,
+    ));
+    replayed.register(CVar::new(
+        "player.name",
+        "Display name.",
+        "anon".to_string(),
+    ));
+
+    replayed.exec(config.as_bytes()).unwrap();
+
+    assert_eq!(replayed.get("r.full_bright").unwrap().get_as_str(), "true");
+    assert_eq!(replayed.get("net.port").unwrap().get_as_str(), "1500");
+    assert_eq!(
+        replayed.get("player.name").unwrap().get_as_str(),
+        "\"someone\""
+    );
+}
+
+#[test]
+fn write_config_skips_non_persistent() {
+    let mut registry = CVarRegistry::new();
+    let session_id = CVar::new("session.id", "Ephemeral session id.", 0u32);
+    session_id.set_persistent(false);
+    registry.register(session_id);
+
+    let mut config = Vec::new();
+    registry.write_config(&mut config).unwrap();
+
+    assert!(String::from_utf8(config).unwrap().is_empty());
+}
+
+#[test]
+fn exec_collects_errors_with_line_numbers_and_keeps_going() {
+    let mut registry = CVarRegistry::new();
+    registry.register(CVar::new("r.full_bright", "Disables all lighting.", false));
+
+    let config = "r.full_bright true\nr.unknown true\nr.full_bright false\n";
+    let errors = registry.exec(config.as_bytes()).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].line, 2);
+    assert_eq!(errors[0].message, "unknown cvar: \"r.unknown\"");
+    assert_eq!(
+        registry.get("r.full_bright").unwrap().get_as_str(),
+        "false"
+    );
+}
+
+#[test]
+fn exec_error_message_is_not_a_debug_dump() {
+    let mut registry = CVarRegistry::new();
+    registry.register(CVar::new_ranged(
+        "net.port",
+        "Listen port.",
+        1024u32,
+        512..=2048,
+    ));
+
+    let errors = registry.exec("net.port 9999\n".as_bytes()).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(!errors[0].message.contains("TooBig"));
+    assert!(errors[0].message.contains("too big"));
+}
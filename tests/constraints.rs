@@ -0,0 +1,31 @@
+use cvars::CVar;
+
+#[test]
+fn ranged_rejects_out_of_range() {
+    let cvar = CVar::new_ranged("net.port", "Listen port.", 1024u32, 512..=2048);
+
+    assert!(cvar.set_from_str("3000").is_err());
+    assert!(cvar.set_from_str("1500").is_ok());
+    assert_eq!(cvar.get(), 1500);
+}
+
+#[test]
+fn ranged_validate_still_surfaces_parse_errors() {
+    let cvar = CVar::new_ranged("net.port", "Listen port.", 1024u32, 512..=2048);
+
+    assert!(cvar.validate("not-a-number").is_err());
+}
+
+#[test]
+fn length_bounded_rejects_out_of_range() {
+    let cvar = CVar::new_length_bounded(
+        "player.name",
+        "Display name.",
+        "anon".to_string(),
+        4..=24,
+    );
+
+    assert!(cvar.set_from_str("\"hi\"").is_err());
+    assert!(cvar.set_from_str("\"someone\"").is_ok());
+    assert_eq!(cvar.get(), "someone");
+}
@@ -0,0 +1,30 @@
+use std::sync::{Arc, Mutex};
+
+use cvars::CVar;
+
+#[test]
+fn callback_runs_on_set() {
+    let cvar = CVar::new("r.full_bright", "Disables all lighting.", false);
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    let seen_clone = seen.clone();
+    cvar.on_change(move |value| seen_clone.lock().unwrap().push(*value));
+
+    cvar.set(true).unwrap();
+    cvar.set_from_str("false").unwrap();
+
+    assert_eq!(*seen.lock().unwrap(), vec![true, false]);
+}
+
+#[test]
+fn callback_does_not_run_on_rejected_set() {
+    let cvar = CVar::new_ranged("net.port", "Listen port.", 1024u32, 512..=2048);
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    let seen_clone = seen.clone();
+    cvar.on_change(move |value| seen_clone.lock().unwrap().push(*value));
+
+    assert!(cvar.set_from_str("9999").is_err());
+
+    assert!(seen.lock().unwrap().is_empty());
+}
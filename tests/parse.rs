@@ -0,0 +1,52 @@
+use cvars::parse_line;
+
+#[test]
+fn splits_name_and_args() {
+    let statements = parse_line("r.full_bright true").unwrap();
+
+    assert_eq!(statements.len(), 1);
+    assert_eq!(statements[0].name, "r.full_bright");
+    assert_eq!(statements[0].args, vec!["true"]);
+}
+
+#[test]
+fn splits_multiple_statements() {
+    let statements = parse_line("map \"dm1\" ctf; r.full_bright true").unwrap();
+
+    assert_eq!(statements.len(), 2);
+    assert_eq!(statements[0].name, "map");
+    assert_eq!(statements[0].args, vec!["\"dm1\"", "ctf"]);
+    assert_eq!(statements[1].name, "r.full_bright");
+    assert_eq!(statements[1].args, vec!["true"]);
+}
+
+#[test]
+fn ignores_comments_and_empty_statements() {
+    let statements = parse_line("// comment\n;; map \"dm1\" // trailing\n").unwrap();
+
+    assert_eq!(statements.len(), 1);
+    assert_eq!(statements[0].name, "map");
+    assert_eq!(statements[0].args, vec!["\"dm1\""]);
+}
+
+#[test]
+fn quoted_token_keeps_escapes_and_quotes() {
+    let statements = parse_line(r#"map "a \"b\" c""#).unwrap();
+
+    assert_eq!(statements[0].args, vec![r#""a \"b\" c""#]);
+}
+
+#[test]
+fn unterminated_quote_is_an_error() {
+    let err = parse_line("map \"dm1").unwrap_err();
+
+    assert_eq!(err.span.start, 4);
+}
+
+#[test]
+fn multi_byte_tokens_do_not_panic() {
+    let statements = parse_line("set \u{2020}").unwrap();
+
+    assert_eq!(statements[0].name, "set");
+    assert_eq!(statements[0].args, vec!["\u{2020}"]);
+}
@@ -0,0 +1,83 @@
+use cvars::{CVar, CVarEnum, CVarRegistry, Span};
+
+#[derive(CVarEnum, Debug, Clone, PartialEq)]
+enum GameMode {
+    #[cvar(alias = "ctf")]
+    CaptureTheFlag,
+    FreeForAll,
+}
+
+#[test]
+fn set_and_get_by_name() {
+    let mut registry = CVarRegistry::new();
+    registry.register(CVar::new("r.full_bright", "Disables all lighting.", false));
+
+    registry.set("r.full_bright", "true").unwrap();
+
+    assert_eq!(registry.get("r.full_bright").unwrap().get_as_str(), "true");
+}
+
+#[test]
+fn unknown_name_is_an_error() {
+    let registry = CVarRegistry::new();
+
+    assert!(registry.set("r.full_bright", "true").is_err());
+}
+
+#[test]
+fn iter_sees_registered_cvars() {
+    let mut registry = CVarRegistry::new();
+    registry.register(CVar::new("r.full_bright", "Disables all lighting.", false));
+    registry.register(CVar::new("r.fov", "Field of view.", 90u32));
+
+    let mut names: Vec<_> = registry.iter().map(|cvar| cvar.name()).collect();
+    names.sort();
+
+    assert_eq!(names, vec!["r.fov", "r.full_bright"]);
+}
+
+#[test]
+fn completes_names_by_prefix() {
+    let mut registry = CVarRegistry::new();
+    registry.register(CVar::new("r.full_bright", "Disables all lighting.", false));
+    registry.register(CVar::new("r.fov", "Field of view.", 90u32));
+    registry.register(CVar::new("s.volume", "Master volume.", 1.0f32));
+
+    let mut names: Vec<_> = registry
+        .complete("r.")
+        .into_iter()
+        .map(|c| c.text)
+        .collect();
+    names.sort();
+
+    assert_eq!(names, vec!["r.fov", "r.full_bright"]);
+}
+
+#[test]
+fn registers_derived_enum_cvars() {
+    let mut registry = CVarRegistry::new();
+    registry.register(CVar::new("g.mode", "Active game mode.", GameMode::FreeForAll));
+
+    registry.set("g.mode", "ctf").unwrap();
+
+    assert_eq!(registry.get("g.mode").unwrap().get_as_str(), "capture_the_flag");
+}
+
+#[test]
+fn completes_values_after_name_and_space() {
+    let mut registry = CVarRegistry::new();
+    registry.register(CVar::new("r.full_bright", "Disables all lighting.", false));
+
+    let completions = registry.complete("r.full_bright t");
+
+    assert_eq!(
+        completions,
+        vec![cvars::Completion {
+            text: "true".to_string(),
+            span: Span {
+                start: "r.full_bright ".len(),
+                end: "r.full_bright t".len(),
+            },
+        }]
+    );
+}